@@ -22,6 +22,25 @@ pub struct RunArgs {
         default_value = "unix:///var/run/docker.sock"
     )]
     pub docker_host: String,
+    /// How long to wait for in-flight execs to finish after a shutdown signal before exiting.
+    #[arg(
+        long,
+        env = "SHUTDOWN_GRACE",
+        default_value = "30s",
+        value_parser = parse_duration
+    )]
+    pub shutdown_grace: std::time::Duration,
+    /// Address to serve Prometheus metrics on (e.g. 0.0.0.0:9090). Disabled if unset.
+    #[arg(long, env = "METRICS_ADDR")]
+    pub metrics_addr: Option<std::net::SocketAddr>,
+    /// Subscribe to the Docker events stream and pick up containers that gain or lose dockcron
+    /// labels after startup, instead of only discovering jobs once at launch.
+    #[arg(long, env = "WATCH")]
+    pub watch: bool,
+}
+
+fn parse_duration(s: &str) -> Result<std::time::Duration, humantime::DurationError> {
+    humantime::parse_duration(s)
 }
 
 impl Cli {
@@ -1,8 +1,9 @@
+use crate::stats::{JobKey, RunResult, Stats};
 use crate::{Label, OverlapPolicy};
 use anyhow::{Context, anyhow};
 use bollard::Docker;
 use bollard::exec::{CreateExecOptions, StartExecResults};
-use bollard::query_parameters::{InspectContainerOptions, ListContainersOptions};
+use bollard::query_parameters::{EventsOptions, InspectContainerOptions, ListContainersOptions};
 use chrono::{DateTime, Utc};
 use cron::Schedule;
 use futures::StreamExt;
@@ -10,10 +11,10 @@ use regex::Regex;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tokio::sync::Semaphore;
-use tokio::time::sleep_until;
 use tracing::{error, info, warn};
 
 #[derive(Debug)]
@@ -24,7 +25,71 @@ pub struct Job {
     pub schedule: JobSchedule,
     pub command: String,
     pub overlap: OverlapPolicy,
-    pub gate: Semaphore, // 1-permit semaphore to guard overlap
+    pub gate: Arc<Semaphore>, // 1-permit semaphore to guard overlap
+    pub retries: u32,
+    pub retry_backoff: Duration,
+}
+
+/// Tracks job runs that are currently in flight (from the first exec attempt through any
+/// retries and backoff sleeps) so shutdown can wait for them to finish instead of exiting
+/// while an exec is still running inside the container.
+#[derive(Debug, Default)]
+pub struct ExecutionTracker {
+    active: Mutex<HashMap<u64, String>>,
+    next_id: AtomicU64,
+}
+
+impl ExecutionTracker {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    fn track(self: &Arc<Self>, container_name: &str, job_name: &str) -> ExecutionGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.active
+            .lock()
+            .unwrap()
+            .insert(id, format!("{container_name}/{job_name}"));
+        ExecutionGuard {
+            id,
+            tracker: self.clone(),
+        }
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.active.lock().unwrap().len()
+    }
+
+    pub fn active_descriptions(&self) -> Vec<String> {
+        self.active.lock().unwrap().values().cloned().collect()
+    }
+}
+
+/// RAII handle for a single in-flight exec. Removing itself on drop (rather than on successful
+/// completion) means a panicking exec task still clears its slot, so the shutdown drain below
+/// can't hang forever on a task that's gone but never deregistered.
+struct ExecutionGuard {
+    id: u64,
+    tracker: Arc<ExecutionTracker>,
+}
+
+impl Drop for ExecutionGuard {
+    fn drop(&mut self) {
+        self.tracker.active.lock().unwrap().remove(&self.id);
+    }
+}
+
+/// Polls until every tracked exec has finished or `grace` elapses, whichever comes first.
+/// Returns `true` if everything drained in time, `false` if the grace period ran out.
+pub async fn await_drain(tracker: &Arc<ExecutionTracker>, grace: Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + grace;
+    while tracker.active_count() > 0 {
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    true
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +102,9 @@ pub enum JobSchedule {
 const JOB_SCHEDULE_EVERY_DEFINITION_PREFIX: &str = "@every ";
 const JOB_SCHEDULE_CRON_DEFINITION_PREFIX: &str = "@cron ";
 
+/// Base backoff used for retries when a job sets `retries` without an explicit `retry-backoff`.
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
 impl Display for JobSchedule {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -112,23 +180,6 @@ pub async fn discover(
         }
 
         let container_id = c.id.clone().unwrap_or_default();
-        let mut found_prefix = None;
-
-        for prefix in prefixes {
-            let enabled_key = format!("{}.enabled", prefix);
-            if labels
-                .get(&enabled_key)
-                .map(|s| s == "true")
-                .unwrap_or(false)
-            {
-                found_prefix = Some(prefix);
-                break;
-            }
-        }
-
-        let Some(prefix) = found_prefix else {
-            continue;
-        };
         let container_name = c
             .names
             .as_ref()
@@ -136,152 +187,357 @@ pub async fn discover(
             .cloned()
             .unwrap_or_else(|| container_id.chars().take(12).collect());
 
-        // Group labels by job name
-        let re = Regex::new(&format!(
-            r"^{}{}([^.]+)\.(schedule|command|no-overlap)$",
-            regex::escape(prefix),
-            regex::escape(".job-exec.")
-        ))
-        .expect("valid regex");
-
-        #[derive(Default)]
-        struct PartialJobConfig {
-            schedule: Option<String>,
-            command: Option<String>,
-            no_overlap: Option<String>,
+        jobs.extend(jobs_from_labels(
+            &container_id,
+            &container_name,
+            &labels,
+            prefixes,
+        )?);
+    }
+
+    Ok(jobs)
+}
+
+/// Re-discovers jobs for a single container, for use by the `--watch` docker-events listener
+/// when a container starts, is updated, or needs re-checking. Returns an empty `Vec` (not an
+/// error) if the container no longer passes the label filter or carries no job labels.
+pub async fn discover_for_container(
+    docker: &Docker,
+    container_id: &str,
+    container_filter_label: Option<&Label>,
+    prefixes: &[String],
+) -> anyhow::Result<Vec<Job>> {
+    let details = docker
+        .inspect_container(container_id, None::<InspectContainerOptions>)
+        .await
+        .context("inspect container")?;
+
+    let labels = details
+        .config
+        .as_ref()
+        .and_then(|c| c.labels.clone())
+        .unwrap_or_default();
+
+    if let Some(container_filter_label) = container_filter_label {
+        let passes_filter = labels
+            .iter()
+            .any(|(k, v)| *k == container_filter_label.key && *v == container_filter_label.value);
+        if !passes_filter {
+            return Ok(Vec::new());
         }
+    }
 
-        let mut by_job: HashMap<String, PartialJobConfig> = HashMap::new();
-
-        for (k, v) in &labels {
-            if let Some(caps) = re.captures(k) {
-                let job = caps[1].to_string();
-                let kind = &caps[2];
-                let acc = by_job.entry(job).or_default();
-                match kind {
-                    "schedule" => acc.schedule = Some(v.clone()),
-                    "command" => acc.command = Some(v.clone()),
-                    "no-overlap" => acc.no_overlap = Some(v.clone()),
-                    _ => {}
-                }
+    let container_name = details
+        .name
+        .clone()
+        .unwrap_or_else(|| container_id.chars().take(12).collect());
+
+    jobs_from_labels(container_id, &container_name, &labels, prefixes)
+}
+
+/// Parses the `<prefix>.job-exec.<name>.*` labels of a single container into `Job`s. Shared by
+/// the bulk startup scan in `discover` and the single-container re-scan in
+/// `discover_for_container`.
+fn jobs_from_labels(
+    container_id: &str,
+    container_name: &str,
+    labels: &HashMap<String, String>,
+    prefixes: &[String],
+) -> anyhow::Result<Vec<Job>> {
+    let mut found_prefix = None;
+    for prefix in prefixes {
+        let enabled_key = format!("{}.enabled", prefix);
+        if labels
+            .get(&enabled_key)
+            .map(|s| s == "true")
+            .unwrap_or(false)
+        {
+            found_prefix = Some(prefix);
+            break;
+        }
+    }
+
+    let Some(prefix) = found_prefix else {
+        return Ok(Vec::new());
+    };
+
+    // Group labels by job name
+    let re = Regex::new(&format!(
+        r"^{}{}([^.]+)\.(schedule|command|no-overlap|retries|retry-backoff)$",
+        regex::escape(prefix),
+        regex::escape(".job-exec.")
+    ))
+    .expect("valid regex");
+
+    #[derive(Default)]
+    struct PartialJobConfig {
+        schedule: Option<String>,
+        command: Option<String>,
+        no_overlap: Option<String>,
+        retries: Option<String>,
+        retry_backoff: Option<String>,
+    }
+
+    let mut by_job: HashMap<String, PartialJobConfig> = HashMap::new();
+
+    for (k, v) in labels {
+        if let Some(caps) = re.captures(k) {
+            let job = caps[1].to_string();
+            let kind = &caps[2];
+            let acc = by_job.entry(job).or_default();
+            match kind {
+                "schedule" => acc.schedule = Some(v.clone()),
+                "command" => acc.command = Some(v.clone()),
+                "no-overlap" => acc.no_overlap = Some(v.clone()),
+                "retries" => acc.retries = Some(v.clone()),
+                "retry-backoff" => acc.retry_backoff = Some(v.clone()),
+                _ => {}
             }
         }
+    }
 
-        for (jobname, acc) in by_job {
-            let (schedule_opt, command_opt, no_overlap_opt) =
-                (acc.schedule, acc.command, acc.no_overlap);
-            let schedule_str = match schedule_opt {
-                Some(s) => s,
-                None => {
-                    warn!(container=%container_name, job=%jobname, "missing schedule label");
-                    continue;
-                }
-            };
-            let command = match command_opt {
-                Some(s) => s,
-                None => {
-                    warn!(container=%container_name, job=%jobname, "missing command label");
-                    continue;
-                }
-            };
+    let mut jobs = Vec::new();
 
-            let schedule = JobSchedule::from_str(&schedule_str)
-                .with_context(|| format!("parse schedule '{}'", schedule_str))?;
+    for (jobname, acc) in by_job {
+        let (schedule_opt, command_opt, no_overlap_opt, retries_opt, retry_backoff_opt) = (
+            acc.schedule,
+            acc.command,
+            acc.no_overlap,
+            acc.retries,
+            acc.retry_backoff,
+        );
+        let schedule_str = match schedule_opt {
+            Some(s) => s,
+            None => {
+                warn!(container=%container_name, job=%jobname, "missing schedule label");
+                continue;
+            }
+        };
+        let command = match command_opt {
+            Some(s) => s,
+            None => {
+                warn!(container=%container_name, job=%jobname, "missing command label");
+                continue;
+            }
+        };
 
-            let overlap = match no_overlap_opt.as_deref().map(|s| s.trim()) {
-                Some("true") => OverlapPolicy::Skip,
-                _ => OverlapPolicy::Allow,
-            };
+        let schedule = JobSchedule::from_str(&schedule_str)
+            .with_context(|| format!("parse schedule '{}'", schedule_str))?;
 
-            jobs.push(Job {
-                container_id: container_id.clone(),
-                container_name: container_name.clone(),
-                name: jobname,
-                schedule,
-                command,
-                overlap,
-                gate: Semaphore::new(1),
-            });
-        }
+        let overlap = match no_overlap_opt.as_deref().map(|s| s.trim()) {
+            Some("true") => OverlapPolicy::Skip,
+            _ => OverlapPolicy::Allow,
+        };
+
+        let retries = match retries_opt {
+            Some(s) => s
+                .trim()
+                .parse::<u32>()
+                .with_context(|| format!("parse retries '{}'", s))?,
+            None => 0,
+        };
+
+        let retry_backoff = match retry_backoff_opt {
+            Some(s) => humantime::parse_duration(s.trim())
+                .with_context(|| format!("parse retry-backoff '{}'", s))?,
+            None => DEFAULT_RETRY_BACKOFF,
+        };
+
+        jobs.push(Job {
+            container_id: container_id.to_string(),
+            container_name: container_name.to_string(),
+            name: jobname,
+            schedule,
+            command,
+            overlap,
+            gate: Arc::new(Semaphore::new(1)),
+            retries,
+            retry_backoff,
+        });
     }
 
     Ok(jobs)
 }
 
-pub async fn run_loop(docker: Docker, job: Job) -> anyhow::Result<()> {
-    info!(
-        container = %job.container_name,
-        job = %job.name,
-        schedule = %job.schedule,
-        overlap_policy = ?job.overlap,
-        "job started"
+/// An instruction produced by [`watch`] and drained by the scheduler to keep the running job set
+/// in sync with the containers actually present.
+pub enum ReconcileEvent {
+    /// Replace all jobs for this container with the given set (possibly empty, e.g. the
+    /// container lost its dockcron labels on an `update`).
+    Upsert {
+        container_id: String,
+        jobs: Vec<Job>,
+    },
+    /// The container stopped or was removed; tear down any jobs still scheduled for it.
+    Remove { container_id: String },
+}
+
+/// Subscribes to the Docker events stream and turns `start`/`update`/`die`/`destroy` container
+/// events into [`ReconcileEvent`]s for the scheduler, so a container that gains or loses
+/// dockcron labels after startup is picked up without a restart. Runs until the events stream
+/// ends or the scheduler drops its receiver.
+pub async fn watch(
+    docker: Docker,
+    container_filter_label: Option<Label>,
+    prefixes: Vec<String>,
+    reconcile_tx: tokio::sync::mpsc::Sender<ReconcileEvent>,
+) -> anyhow::Result<()> {
+    let mut filters = HashMap::new();
+    filters.insert("type".to_string(), vec!["container".to_string()]);
+    filters.insert(
+        "event".to_string(),
+        vec![
+            "start".to_string(),
+            "die".to_string(),
+            "destroy".to_string(),
+            "update".to_string(),
+        ],
     );
-    let docker = Arc::new(docker);
-    let job = Arc::new(job);
-
-    match job.schedule.clone() {
-        JobSchedule::Every(repeat_duration) => {
-            let mut execution_interval = tokio::time::interval(repeat_duration);
-            execution_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Burst);
-
-            // Mirror ofelia logic: wait one tick before starting the first execution
-            execution_interval.tick().await;
-
-            loop {
-                execution_interval.tick().await;
-                match job.overlap {
-                    OverlapPolicy::Allow => run_once_async(docker.clone(), job.clone()).await,
-                    OverlapPolicy::Skip => {
-                        if let Ok(permit) = job.gate.try_acquire() {
-                            run_once_async(docker.clone(), job.clone()).await;
-                            drop(permit);
-                        } else {
-                            info!(container=%job.container_name, job=%job.name, "skipping tick (policy={:?}: previous run still in progress)", job.overlap);
-                        }
+
+    let mut events = docker.events(Some(EventsOptions {
+        filters,
+        ..Default::default()
+    }));
+
+    info!("watching docker events for container start/die/destroy/update");
+
+    while let Some(event) = events.next().await {
+        let event = match event {
+            Ok(event) => event,
+            Err(e) => {
+                warn!(error = ?e, "docker events stream error");
+                continue;
+            }
+        };
+
+        let Some(container_id) = event.actor.and_then(|a| a.id) else {
+            continue;
+        };
+
+        let reconcile_event = match event.action.as_deref() {
+            Some("start") | Some("update") => {
+                match discover_for_container(
+                    &docker,
+                    &container_id,
+                    container_filter_label.as_ref(),
+                    &prefixes,
+                )
+                .await
+                {
+                    Ok(jobs) => ReconcileEvent::Upsert { container_id, jobs },
+                    Err(e) => {
+                        warn!(container_id = %container_id, error = ?e, "failed to re-discover container after event");
+                        continue;
                     }
                 }
             }
+            Some("die") | Some("destroy") => ReconcileEvent::Remove { container_id },
+            _ => continue,
+        };
+
+        if reconcile_tx.send(reconcile_event).await.is_err() {
+            // Scheduler is gone; nothing left to reconcile against.
+            break;
         }
-        JobSchedule::Cron(schedule) => {
-            let mut next = next_instant(*schedule.clone())?;
-            loop {
-                sleep_until(next).await;
-
-                match job.overlap {
-                    OverlapPolicy::Allow => run_once_async(docker.clone(), job.clone()).await,
-                    OverlapPolicy::Skip => {
-                        if let Ok(permit) = job.gate.try_acquire() {
-                            run_once_async(docker.clone(), job.clone()).await;
-                            drop(permit);
-                        } else {
-                            info!(container=%job.container_name, job=%job.name, "skipping tick (no-overlap: previous run still in progress)");
-                        }
-                    }
-                }
+    }
 
-                next = next_instant(*schedule.clone())?;
-            }
+    Ok(())
+}
+
+/// Decides whether this tick's exec should run (honoring the overlap policy) and, if so, spawns
+/// it on its own task so the scheduler loop driving it is never blocked waiting on the exec to
+/// finish.
+pub(crate) fn dispatch(docker: Arc<Docker>, job: Arc<Job>, tracker: Arc<ExecutionTracker>, stats: Stats) {
+    match job.overlap {
+        OverlapPolicy::Allow => {
+            tokio::spawn(run_once_async(docker, job, tracker, stats));
         }
+        OverlapPolicy::Skip => match job.gate.clone().try_acquire_owned() {
+            Ok(permit) => {
+                tokio::spawn(async move {
+                    run_once_async(docker, job, tracker, stats).await;
+                    drop(permit);
+                });
+            }
+            Err(_) => {
+                info!(container=%job.container_name, job=%job.name, "skipping tick (policy={:?}: previous run still in progress)", job.overlap);
+            }
+        },
     }
 }
 
-async fn run_once_async(docker: Arc<Docker>, job: Arc<Job>) {
-    let container_name = job.container_name.clone();
-    let job_name = job.name.clone();
+async fn run_once_async(docker: Arc<Docker>, job: Arc<Job>, tracker: Arc<ExecutionTracker>, stats: Stats) {
+    let _guard = tracker.track(&job.container_name, &job.name);
+    let started_at = Utc::now();
+    let started = tokio::time::Instant::now();
+
+    let result = run_with_retries(docker, job.clone()).await;
+    let duration = started.elapsed();
 
-    let spawn_result = tokio::spawn(async move {
-        if let Err(e) = run_once(docker, job.clone()).await {
+    let run_result = match &result {
+        Ok(ExecOutcome::NonZeroExit(code)) => {
+            warn!(container=%job.container_name, job=%job.name, exit_code = code, "exec completed with non-zero exit code");
+            Some(RunResult::NonZeroExit(*code))
+        }
+        Ok(ExecOutcome::Success) => Some(RunResult::Success),
+        Ok(ExecOutcome::Skipped) => None,
+        Err(e) => {
             error!(container=%job.container_name, job=%job.name, error=?e, "execution failed");
+            Some(RunResult::Error)
+        }
+    };
+
+    if let Some(run_result) = run_result {
+        let key = JobKey {
+            container_name: job.container_name.clone(),
+            job_name: job.name.clone(),
+        };
+        stats.record(key, started_at, duration, run_result);
+    }
+}
+
+/// Runs `job`, retrying a transient failure (non-zero exit or a Docker error) up to
+/// `job.retries` times with exponential backoff before giving up for this tick, so a hiccup
+/// inside the container self-heals without waiting for the next schedule tick.
+async fn run_with_retries(docker: Arc<Docker>, job: Arc<Job>) -> anyhow::Result<ExecOutcome> {
+    let mut attempt = 1;
+    let mut backoff = job.retry_backoff;
+
+    loop {
+        let result = run_once(docker.clone(), job.clone()).await;
+        let transient_failure = matches!(result, Err(_) | Ok(ExecOutcome::NonZeroExit(_)));
+
+        if !transient_failure || attempt > job.retries {
+            return result;
         }
-    })
-    .await;
 
-    if let Err(e) = spawn_result {
-        error!(container=%container_name, job=%job_name, error=?e, "could not spawn execution task");
+        warn!(
+            container = %job.container_name,
+            job = %job.name,
+            attempt,
+            max_attempts = job.retries + 1,
+            backoff = %humantime::format_duration(backoff),
+            "exec failed; retrying after backoff"
+        );
+        tokio::time::sleep(backoff).await;
+        backoff *= 2;
+        attempt += 1;
     }
 }
 
-async fn run_once(docker: Arc<Docker>, job: Arc<Job>) -> anyhow::Result<()> {
+/// Outcome of a single exec attempt. A dispatch error (a Docker API failure while creating,
+/// starting or inspecting the exec) is surfaced as `Err` instead. Feeds the retry and metrics
+/// subsystems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecOutcome {
+    Success,
+    NonZeroExit(i64),
+    /// Exec never ran because the container could not be inspected, wasn't running, or the
+    /// command was empty.
+    Skipped,
+}
+
+async fn run_once(docker: Arc<Docker>, job: Arc<Job>) -> anyhow::Result<ExecOutcome> {
     info!(container=%job.container_name, job=%job.name, "exec starting");
 
     // Check if container is running
@@ -297,12 +553,12 @@ async fn run_once(docker: Arc<Docker>, job: Arc<Job>) -> anyhow::Result<()> {
                 .unwrap_or(false);
             if !running {
                 warn!(container=%job.container_name, job=%job.name, "container is not running; skipping exec");
-                return Ok(());
+                return Ok(ExecOutcome::Skipped);
             }
         }
         Err(e) => {
             warn!(container=%job.container_name, job=%job.name, error=?e, "failed to inspect container; skipping exec");
-            return Ok(());
+            return Ok(ExecOutcome::Skipped);
         }
     }
 
@@ -310,7 +566,7 @@ async fn run_once(docker: Arc<Docker>, job: Arc<Job>) -> anyhow::Result<()> {
         Some(args) if !args.is_empty() => args,
         _ => {
             warn!(container=%job.container_name, job=%job.name, "exec command is empty or erroneous");
-            return Ok(());
+            return Ok(ExecOutcome::Skipped);
         }
     };
 
@@ -352,11 +608,21 @@ async fn run_once(docker: Arc<Docker>, job: Arc<Job>) -> anyhow::Result<()> {
         }
     }
 
-    info!(container=%job.container_name, job=%job.name, "exec finished");
-    Ok(())
+    let exit_code = docker
+        .inspect_exec(&exec)
+        .await
+        .context("inspect exec")?
+        .exit_code;
+
+    info!(container=%job.container_name, job=%job.name, exit_code = ?exit_code, "exec finished");
+
+    Ok(match exit_code {
+        Some(0) | None => ExecOutcome::Success,
+        Some(code) => ExecOutcome::NonZeroExit(code),
+    })
 }
 
-fn next_instant(schedule: Schedule) -> anyhow::Result<tokio::time::Instant> {
+pub(crate) fn next_instant(schedule: Schedule) -> anyhow::Result<tokio::time::Instant> {
     let now: DateTime<Utc> = Utc::now();
     let next_dt = schedule
         .upcoming(Utc)
@@ -1,11 +1,12 @@
 mod cli;
 mod job;
+mod scheduler;
+mod stats;
 
 use crate::cli::{Command, RunArgs};
 use anyhow::{Result, anyhow};
 use bollard::Docker;
 use std::str::FromStr;
-use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
 #[derive(Debug, Clone, Copy)]
@@ -46,7 +47,7 @@ async fn main() -> Result<()> {
     }
 }
 
-async fn run(args: RunArgs, mut shutdown: tokio::sync::broadcast::Receiver<()>) -> Result<()> {
+async fn run(args: RunArgs, shutdown: tokio::sync::broadcast::Receiver<()>) -> Result<()> {
     let docker = docker_client(args.docker_host)?;
 
     let container_label_selector = args.container_label_selector.map(|selector| {
@@ -62,40 +63,57 @@ async fn run(args: RunArgs, mut shutdown: tokio::sync::broadcast::Receiver<()>)
         },
     };
 
-    let jobs = job::discover(&docker, container_label_selector, &args.label_prefixes).await?;
-    if jobs.is_empty() {
+    let jobs = job::discover(&docker, container_label_selector.clone(), &args.label_prefixes).await?;
+    if jobs.is_empty() && !args.watch {
         warn!("no jobs discovered; make sure labels are set and Docker is reachable");
         return Ok(());
     }
-
     info!(count = jobs.len(), "starting jobs");
 
-    let mut handles: Vec<JoinHandle<()>> = Vec::new();
-    for job in jobs {
-        let docker = docker.clone();
-        let mut shutdown_rx = shutdown.resubscribe();
-        handles.push(tokio::spawn(async move {
-            tokio::select! {
-                res = job::run_loop(docker, job) => {
-                    if let Err(e) = res {
-                        error!(error = ?e, "job loop terminated with error");
-                    }
-                }
-                _ = shutdown_rx.recv() => {
-                    info!("job shutdown requested");
-                }
+    let tracker = job::ExecutionTracker::new();
+    let stats = stats::Stats::new();
+
+    if let Some(addr) = args.metrics_addr {
+        let stats = stats.clone();
+        tokio::spawn(async move {
+            if let Err(e) = stats::serve(addr, stats).await {
+                error!(error = ?e, "metrics server exited");
             }
-        }));
+        });
     }
 
-    tokio::select! {
-        _ = async {
-            for handle in handles {
-                let _ = handle.await; // errors already logged inside task
+    let reconcile_rx = if args.watch {
+        let (tx, rx) = tokio::sync::mpsc::channel(64);
+        let watch_docker = docker.clone();
+        let watch_filter = container_label_selector.clone();
+        let watch_prefixes = args.label_prefixes.clone();
+        tokio::spawn(async move {
+            if let Err(e) = job::watch(watch_docker, watch_filter, watch_prefixes, tx).await {
+                error!(error = ?e, "docker events watcher exited");
             }
-        } => {}
-        _ = shutdown.recv() => {
-            info!("graceful shutdown initiated");
+        });
+        Some(rx)
+    } else {
+        None
+    };
+
+    // The scheduler stops dispatching new ticks as soon as it sees the shutdown signal and
+    // returns; it does not wait for in-flight execs.
+    if let Err(e) = scheduler::run(docker, jobs, shutdown, tracker.clone(), stats, reconcile_rx).await {
+        error!(error = ?e, "scheduler terminated with error");
+    }
+
+    if tracker.active_count() > 0 {
+        info!(
+            grace = %humantime::format_duration(args.shutdown_grace),
+            count = tracker.active_count(),
+            "dispatch stopped; awaiting in-flight executions before exit"
+        );
+        if !job::await_drain(&tracker, args.shutdown_grace).await {
+            warn!(
+                remaining = ?tracker.active_descriptions(),
+                "shutdown grace period elapsed with executions still running"
+            );
         }
     }
 
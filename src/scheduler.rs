@@ -0,0 +1,191 @@
+use crate::job::{self, ExecutionTracker, Job, JobSchedule, ReconcileEvent};
+use crate::stats::Stats;
+use bollard::Docker;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::{Instant, sleep_until};
+use tracing::info;
+
+/// Far-off next-fire time used while the heap is empty, so the timer arm of the scheduler loop
+/// stays idle while the loop waits for shutdown or reconciliation.
+const IDLE_POLL: Duration = Duration::from_secs(365 * 24 * 3600);
+
+/// A job's next scheduled fire time, ordered so a `BinaryHeap` (a max-heap) yields the earliest
+/// `next` first.
+struct ScheduledEntry {
+    next: Instant,
+    job_id: usize,
+}
+
+impl PartialEq for ScheduledEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.next == other.next
+    }
+}
+
+impl Eq for ScheduledEntry {}
+
+impl PartialOrd for ScheduledEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScheduledEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.next.cmp(&self.next)
+    }
+}
+
+/// Drives every job from a single timer instead of one tokio task per job: a binary heap keyed
+/// by next-fire `Instant` is peeked, slept to, popped, dispatched, and re-pushed with its next
+/// fire time. Centralizing timing this way makes graceful shutdown a matter of no longer peeking
+/// the heap, and gives dynamic job add/remove a single structure to mutate.
+pub async fn run(
+    docker: Docker,
+    jobs: Vec<Job>,
+    mut shutdown: tokio::sync::broadcast::Receiver<()>,
+    tracker: Arc<ExecutionTracker>,
+    stats: Stats,
+    mut reconcile: Option<tokio::sync::mpsc::Receiver<ReconcileEvent>>,
+) -> anyhow::Result<()> {
+    let docker = Arc::new(docker);
+
+    let mut heap = BinaryHeap::new();
+    let mut jobs_by_id = HashMap::new();
+    // Lazily-deleted job ids: an entry may already be sitting in the heap when its job is
+    // removed, since the heap itself supports no efficient removal. Popping one of these just
+    // drops it instead of dispatching and re-queuing it.
+    let mut removed = HashSet::new();
+    let mut next_job_id = 0usize;
+
+    for job in jobs {
+        schedule_job(job, &mut heap, &mut jobs_by_id, &mut next_job_id)?;
+    }
+
+    loop {
+        let next_fire = heap.peek().map(|entry| entry.next).unwrap_or_else(|| Instant::now() + IDLE_POLL);
+
+        let reconcile_next = async {
+            match reconcile.as_mut() {
+                Some(rx) => rx.recv().await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            _ = sleep_until(next_fire) => {
+                // The heap may be empty here if the idle deadline elapsed.
+                let Some(entry) = heap.pop() else {
+                    continue;
+                };
+                if removed.remove(&entry.job_id) {
+                    continue;
+                }
+                let job = jobs_by_id[&entry.job_id].clone();
+                job::dispatch(docker.clone(), job.clone(), tracker.clone(), stats.clone());
+                let next = next_fire_after(&job.schedule, entry.next)?;
+                heap.push(ScheduledEntry { next, job_id: entry.job_id });
+            }
+            _ = shutdown.recv() => {
+                info!("stopping scheduler; shutdown requested");
+                break;
+            }
+            maybe_event = reconcile_next => {
+                match maybe_event {
+                    Some(event) => apply_reconcile_event(
+                        event,
+                        &mut heap,
+                        &mut jobs_by_id,
+                        &mut removed,
+                        &mut next_job_id,
+                    )?,
+                    None => {
+                        // The watch task ended (events stream closed); stop polling it.
+                        reconcile = None;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn schedule_job(
+    job: Job,
+    heap: &mut BinaryHeap<ScheduledEntry>,
+    jobs_by_id: &mut HashMap<usize, Arc<Job>>,
+    next_job_id: &mut usize,
+) -> anyhow::Result<()> {
+    info!(
+        container = %job.container_name,
+        job = %job.name,
+        schedule = %job.schedule,
+        overlap_policy = ?job.overlap,
+        "job scheduled"
+    );
+    let job_id = *next_job_id;
+    *next_job_id += 1;
+    let next = initial_fire(&job.schedule)?;
+    heap.push(ScheduledEntry { next, job_id });
+    jobs_by_id.insert(job_id, Arc::new(job));
+    Ok(())
+}
+
+fn apply_reconcile_event(
+    event: ReconcileEvent,
+    heap: &mut BinaryHeap<ScheduledEntry>,
+    jobs_by_id: &mut HashMap<usize, Arc<Job>>,
+    removed: &mut HashSet<usize>,
+    next_job_id: &mut usize,
+) -> anyhow::Result<()> {
+    match event {
+        ReconcileEvent::Upsert { container_id, jobs } => {
+            remove_jobs_for_container(&container_id, jobs_by_id, removed);
+            for job in jobs {
+                schedule_job(job, heap, jobs_by_id, next_job_id)?;
+            }
+        }
+        ReconcileEvent::Remove { container_id } => {
+            remove_jobs_for_container(&container_id, jobs_by_id, removed);
+        }
+    }
+    Ok(())
+}
+
+fn remove_jobs_for_container(
+    container_id: &str,
+    jobs_by_id: &mut HashMap<usize, Arc<Job>>,
+    removed: &mut HashSet<usize>,
+) {
+    let ids: Vec<usize> = jobs_by_id
+        .iter()
+        .filter(|(_, job)| job.container_id == container_id)
+        .map(|(id, _)| *id)
+        .collect();
+
+    for id in ids {
+        if let Some(job) = jobs_by_id.remove(&id) {
+            info!(container = %job.container_name, job = %job.name, "job removed");
+        }
+        removed.insert(id);
+    }
+}
+
+fn initial_fire(schedule: &JobSchedule) -> anyhow::Result<Instant> {
+    match schedule {
+        // Mirror ofelia logic: wait one period before starting the first execution.
+        JobSchedule::Every(period) => Ok(Instant::now() + *period),
+        JobSchedule::Cron(schedule) => job::next_instant((**schedule).clone()),
+    }
+}
+
+fn next_fire_after(schedule: &JobSchedule, previous: Instant) -> anyhow::Result<Instant> {
+    match schedule {
+        JobSchedule::Every(period) => Ok(previous + *period),
+        JobSchedule::Cron(schedule) => job::next_instant((**schedule).clone()),
+    }
+}
@@ -0,0 +1,193 @@
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+/// Identifies a job for stats bookkeeping. Mirrors the `(container, job)` pairing used
+/// throughout `job.rs`'s tracing fields.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JobKey {
+    pub container_name: String,
+    pub job_name: String,
+}
+
+/// Final outcome of one scheduled run (after any retries), as far as stats bookkeeping cares.
+/// Runs whose final attempt was skipped (container not inspectable or not running, empty
+/// command) are not recorded.
+#[derive(Debug, Clone, Copy)]
+pub enum RunResult {
+    Success,
+    NonZeroExit(i64),
+    Error,
+}
+
+#[derive(Debug, Clone, Default)]
+struct JobStats {
+    last_start: Option<DateTime<Utc>>,
+    last_duration: Option<Duration>,
+    last_exit_code: Option<i64>,
+    total_runs: u64,
+    success_count: u64,
+    failure_count: u64,
+}
+
+/// Per-job execution history, updated from `job::run_once_async` and rendered as Prometheus text
+/// for the `--metrics-addr` HTTP endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct Stats(Arc<Mutex<HashMap<JobKey, JobStats>>>);
+
+impl Stats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, key: JobKey, started_at: DateTime<Utc>, duration: Duration, result: RunResult) {
+        let mut jobs = self.0.lock().unwrap();
+        let stats = jobs.entry(key).or_default();
+        stats.last_start = Some(started_at);
+        stats.last_duration = Some(duration);
+        stats.total_runs += 1;
+        match result {
+            RunResult::Success => {
+                stats.last_exit_code = Some(0);
+                stats.success_count += 1;
+            }
+            RunResult::NonZeroExit(code) => {
+                stats.last_exit_code = Some(code);
+                stats.failure_count += 1;
+            }
+            RunResult::Error => {
+                stats.failure_count += 1;
+            }
+        }
+    }
+
+    fn render_prometheus(&self) -> String {
+        let jobs = self.0.lock().unwrap();
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# HELP dockcron_job_runs_total Total runs (retries included in their run), by final result.");
+        let _ = writeln!(out, "# TYPE dockcron_job_runs_total counter");
+        for (key, s) in jobs.iter() {
+            let _ = writeln!(
+                out,
+                "dockcron_job_runs_total{{container=\"{}\",job=\"{}\",result=\"success\"}} {}",
+                escape_label(&key.container_name),
+                escape_label(&key.job_name),
+                s.success_count
+            );
+            let _ = writeln!(
+                out,
+                "dockcron_job_runs_total{{container=\"{}\",job=\"{}\",result=\"failure\"}} {}",
+                escape_label(&key.container_name),
+                escape_label(&key.job_name),
+                s.failure_count
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP dockcron_job_last_duration_seconds Duration of the most recent run, including retries and backoff."
+        );
+        let _ = writeln!(out, "# TYPE dockcron_job_last_duration_seconds gauge");
+        for (key, s) in jobs.iter() {
+            if let Some(d) = s.last_duration {
+                let _ = writeln!(
+                    out,
+                    "dockcron_job_last_duration_seconds{{container=\"{}\",job=\"{}\"}} {}",
+                    escape_label(&key.container_name),
+                    escape_label(&key.job_name),
+                    d.as_secs_f64()
+                );
+            }
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP dockcron_job_last_exit_code Exit code of the most recent run that reported one."
+        );
+        let _ = writeln!(out, "# TYPE dockcron_job_last_exit_code gauge");
+        for (key, s) in jobs.iter() {
+            if let Some(code) = s.last_exit_code {
+                let _ = writeln!(
+                    out,
+                    "dockcron_job_last_exit_code{{container=\"{}\",job=\"{}\"}} {}",
+                    escape_label(&key.container_name),
+                    escape_label(&key.job_name),
+                    code
+                );
+            }
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP dockcron_job_last_start_timestamp_seconds Unix time the most recent exec started."
+        );
+        let _ = writeln!(out, "# TYPE dockcron_job_last_start_timestamp_seconds gauge");
+        for (key, s) in jobs.iter() {
+            if let Some(start) = s.last_start {
+                let _ = writeln!(
+                    out,
+                    "dockcron_job_last_start_timestamp_seconds{{container=\"{}\",job=\"{}\"}} {}",
+                    escape_label(&key.container_name),
+                    escape_label(&key.job_name),
+                    start.timestamp()
+                );
+            }
+        }
+
+        out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Serves `stats` as Prometheus text format on `addr` until the process exits. Hand-rolled
+/// instead of pulling in a web framework, since this endpoint only ever returns one document.
+pub async fn serve(addr: SocketAddr, stats: Stats) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("bind metrics listener on {addr}"))?;
+    info!(%addr, "metrics endpoint listening");
+
+    loop {
+        let (mut socket, _) = match listener.accept().await {
+            Ok(pair) => pair,
+            Err(e) => {
+                warn!(error = ?e, "failed to accept metrics connection");
+                continue;
+            }
+        };
+        let stats = stats.clone();
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(&mut socket, &stats).await {
+                warn!(error = ?e, "metrics connection error");
+            }
+        });
+    }
+}
+
+async fn handle_connection(socket: &mut tokio::net::TcpStream, stats: &Stats) -> anyhow::Result<()> {
+    // We only ever serve one document regardless of method/path, so just read (up to 1 KiB of)
+    // the request and ignore it.
+    let mut buf = [0u8; 1024];
+    let _ = socket.read(&mut buf).await?;
+
+    let body = stats.render_prometheus();
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    socket.write_all(response.as_bytes()).await?;
+    socket.shutdown().await?;
+    Ok(())
+}